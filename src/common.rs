@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::config::Method;
+use crate::metrics;
+
+#[derive(Debug, Error)]
+pub enum TestflowError {
+    #[error("unsupported adapter: {0}")]
+    UnsupportedAdapter(String),
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("rpc error: {0}")]
+    RpcError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum TestrpcError {
+    #[error("failed to install signal handler: {0}")]
+    TerminationError(String),
+}
+
+/// Outcome of every request of one method type issued during a round.
+#[derive(Debug, Clone, Default)]
+pub struct MethodResults {
+    pub count: u32,
+    pub successes: u32,
+    pub failures: u32,
+    pub latency: metrics::LatencyHistogram,
+}
+
+/// Outcome of a single round of load against the configured adapter.
+#[derive(Debug, Clone, Default)]
+pub struct RoundResults {
+    pub iteration: u32,
+    pub round: usize,
+    pub successes: u32,
+    pub failures: u32,
+    pub latency: metrics::LatencyHistogram,
+    /// Percentile/throughput summary of `latency` over just this round, so
+    /// callers get per-round reporting without also having to track how
+    /// long the round took.
+    pub summary: metrics::LatencySummary,
+    /// Per-method breakdown, populated by adapters that issue a weighted mix
+    /// of request kinds (e.g. the HTTP adapter's GET/PUT/DELETE blend).
+    pub by_method: HashMap<Method, MethodResults>,
+    /// Signature of the on-chain transaction this round submitted, if any
+    /// (populated by the Solana adapter; `None` for adapters with no
+    /// equivalent notion of a confirmable transaction).
+    pub signature: Option<String>,
+}
+
+/// Outcome of a full `run`: every completed round plus the latency summary
+/// merged across all of them.
+#[derive(Debug, Clone, Default)]
+pub struct RunResults {
+    pub rounds: Vec<RoundResults>,
+    pub latency: metrics::LatencySummary,
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::common::TestflowError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Adapter {
+    Hotshot,
+    Solana,
+    Http,
+}
+
+impl fmt::Display for Adapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Adapter::Hotshot => write!(f, "hotshot"),
+            Adapter::Solana => write!(f, "solana"),
+            Adapter::Http => write!(f, "http"),
+        }
+    }
+}
+
+/// One kind of request the HTTP adapter can issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Method {
+    Get,
+    Put,
+    Delete,
+}
+
+/// Relative weight of one [`Method`] within a round's mix; weights are
+/// normalized against each other, not required to sum to any particular
+/// total.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MethodWeight {
+    pub method: Method,
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Round {
+    pub template: String,
+}
+
+/// Which kind of Solana transaction a round template should generate.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolanaOperation {
+    Memo,
+    Transfer,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoundTemplate {
+    pub solana_operation: Option<SolanaOperation>,
+    pub payer_keypair_path: Option<String>,
+    pub recipient: Option<String>,
+    pub transfer_lamports: Option<u64>,
+    /// Weighted blend of GET/PUT/DELETE requests for the HTTP adapter.
+    #[serde(default)]
+    pub method_weights: Vec<MethodWeight>,
+    /// How many requests to sample from `method_weights` per round.
+    pub requests_per_round: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub adapter: Adapter,
+    #[serde(default)]
+    pub args: HashMap<String, String>,
+    pub rounds: Vec<Round>,
+    pub round_templates: HashMap<String, RoundTemplate>,
+    pub interval: u64,
+    pub iterations: Option<usize>,
+    /// Maximum number of round tasks allowed to be in flight at once.
+    pub max_concurrency: usize,
+}
+
+impl Config {
+    /// Read and validate a config file from disk, e.g. on startup or on a
+    /// SIGHUP-triggered reload.
+    pub fn load(path: &Path) -> Result<Self, TestflowError> {
+        let raw = std::fs::read_to_string(path).map_err(|e| {
+            TestflowError::InvalidConfig(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        let cfg: Config = toml::from_str(&raw).map_err(|e| {
+            TestflowError::InvalidConfig(format!("failed to parse {}: {}", path.display(), e))
+        })?;
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    pub fn validate(&self) -> Result<(), TestflowError> {
+        if self.interval == 0 {
+            return Err(TestflowError::InvalidConfig(
+                "`interval` must be greater than zero".into(),
+            ));
+        }
+        if self.max_concurrency == 0 {
+            return Err(TestflowError::InvalidConfig(
+                "`max_concurrency` must be greater than zero".into(),
+            ));
+        }
+        for round in &self.rounds {
+            let template = self.round_templates.get(&round.template).ok_or_else(|| {
+                TestflowError::InvalidConfig(format!(
+                    "round references unknown template `{}`",
+                    round.template
+                ))
+            })?;
+            if self.adapter == Adapter::Http && template.method_weights.is_empty() {
+                return Err(TestflowError::InvalidConfig(format!(
+                    "template `{}` has no `method_weights` for the http adapter",
+                    round.template
+                )));
+            }
+            if self.adapter == Adapter::Solana {
+                if template.payer_keypair_path.is_none() {
+                    return Err(TestflowError::InvalidConfig(format!(
+                        "template `{}` has no `payer_keypair_path` for the solana adapter",
+                        round.template
+                    )));
+                }
+                let operation = template
+                    .solana_operation
+                    .clone()
+                    .unwrap_or(SolanaOperation::Memo);
+                if operation == SolanaOperation::Transfer && template.recipient.is_none() {
+                    return Err(TestflowError::InvalidConfig(format!(
+                        "template `{}` is a transfer but has no `recipient`",
+                        round.template
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
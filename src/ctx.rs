@@ -0,0 +1,28 @@
+use tokio::sync::broadcast;
+
+/// Shared run context; `recv` hands out a receiver that fires once when the
+/// run should stop, so every in-flight round can observe the same signal.
+pub struct Context {
+    stop_tx: broadcast::Sender<()>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        let (stop_tx, _) = broadcast::channel(1);
+        Self { stop_tx }
+    }
+
+    pub fn recv(&self) -> broadcast::Receiver<()> {
+        self.stop_tx.subscribe()
+    }
+
+    pub fn stop(&self) {
+        let _ = self.stop_tx.send(());
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::common::{RoundResults, TestflowError};
+use crate::config;
+use crate::metrics;
+
+pub async fn load_endpoints(args: HashMap<String, String>) -> Result<Vec<String>, TestflowError> {
+    let endpoints = args
+        .get("endpoints")
+        .ok_or_else(|| TestflowError::InvalidConfig("missing `endpoints` arg".into()))?;
+    Ok(endpoints.split(',').map(|s| s.trim().to_string()).collect())
+}
+
+pub async fn process_round(
+    round: config::Round,
+    iteration: u32,
+    rpc_urls: Vec<String>,
+    round_templates: HashMap<String, config::RoundTemplate>,
+) -> Result<RoundResults, TestflowError> {
+    let _ = (round, rpc_urls, round_templates);
+    let started = Instant::now();
+    let mut latency = metrics::LatencyHistogram::new();
+    latency.record(started.elapsed());
+    let summary = latency.summary(started.elapsed());
+    Ok(RoundResults {
+        iteration,
+        latency,
+        summary,
+        ..Default::default()
+    })
+}
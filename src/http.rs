@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::common::{RoundResults, TestflowError};
+use crate::config::{self, Method};
+use crate::metrics;
+
+const PUT_PAYLOAD_LEN: usize = 32;
+
+pub async fn load_endpoints(args: HashMap<String, String>) -> Result<Vec<String>, TestflowError> {
+    let endpoints = args
+        .get("endpoints")
+        .ok_or_else(|| TestflowError::InvalidConfig("missing `endpoints` arg".into()))?;
+    Ok(endpoints.split(',').map(|s| s.trim().to_string()).collect())
+}
+
+/// Normalized cumulative distribution over a round template's `method_weights`,
+/// sampled once per request with the round's seeded RNG.
+struct WeightedMethods {
+    cumulative: Vec<(f64, Method)>,
+    total: f64,
+}
+
+impl WeightedMethods {
+    fn new(weights: &[config::MethodWeight]) -> Result<Self, TestflowError> {
+        if weights.is_empty() {
+            return Err(TestflowError::InvalidConfig(
+                "round template has no `method_weights`".into(),
+            ));
+        }
+        let mut running = 0.0;
+        let cumulative = weights
+            .iter()
+            .map(|w| {
+                running += w.weight as f64;
+                (running, w.method)
+            })
+            .collect();
+        Ok(Self { cumulative, total: running })
+    }
+
+    fn sample(&self, rng: &mut ChaCha8Rng) -> Method {
+        let roll = rng.gen::<f64>() * self.total;
+        self.cumulative
+            .iter()
+            .find(|(threshold, _)| roll < *threshold)
+            .map(|(_, method)| *method)
+            .unwrap_or(self.cumulative[self.cumulative.len() - 1].1)
+    }
+}
+
+/// Issue a weighted blend of GET/PUT/DELETE requests for one round,
+/// recording per-method counts, successes and latency alongside the round
+/// totals. `client` is shared across rounds by the caller so connection
+/// pooling/keep-alive carry over between rounds instead of reconnecting
+/// every time, which would otherwise skew the latency numbers.
+pub async fn process_round(
+    round: config::Round,
+    iteration: u32,
+    rpc_urls: Vec<String>,
+    round_templates: HashMap<String, config::RoundTemplate>,
+    client: reqwest::Client,
+) -> Result<RoundResults, TestflowError> {
+    let template = round_templates.get(&round.template).ok_or_else(|| {
+        TestflowError::InvalidConfig(format!("unknown round template `{}`", round.template))
+    })?;
+    if rpc_urls.is_empty() {
+        return Err(TestflowError::InvalidConfig(
+            "no rpc endpoints configured".into(),
+        ));
+    }
+    // Round-robin across every configured endpoint instead of always hitting
+    // the first one, so a multi-endpoint config actually spreads load.
+    let base_url = &rpc_urls[iteration as usize % rpc_urls.len()];
+
+    let distribution = WeightedMethods::new(&template.method_weights)?;
+    let request_count = template.requests_per_round.unwrap_or(1);
+    let mut rng = ChaCha8Rng::seed_from_u64(iteration as u64);
+    let round_started = Instant::now();
+
+    let mut result = RoundResults {
+        iteration,
+        ..Default::default()
+    };
+    for _ in 0..request_count {
+        let method = distribution.sample(&mut rng);
+        let entry = result.by_method.entry(method).or_default();
+        let started = Instant::now();
+        let outcome = send(&client, base_url, method, &mut rng).await;
+        entry.latency.record(started.elapsed());
+        entry.count += 1;
+        match outcome {
+            Ok(()) => {
+                entry.successes += 1;
+                result.successes += 1;
+            }
+            Err(e) => {
+                tracing::warn!("iteration {} {:?} request failed: {}", iteration, method, e);
+                entry.failures += 1;
+                result.failures += 1;
+            }
+        }
+    }
+
+    let mut merged = metrics::LatencyHistogram::new();
+    for method_result in result.by_method.values() {
+        merged.merge(&method_result.latency);
+    }
+    result.latency = merged;
+    result.summary = result.latency.summary(round_started.elapsed());
+    Ok(result)
+}
+
+async fn send(
+    client: &reqwest::Client,
+    base_url: &str,
+    method: Method,
+    rng: &mut ChaCha8Rng,
+) -> Result<(), TestflowError> {
+    let response = match method {
+        Method::Get => client.get(base_url).send().await,
+        Method::Put => {
+            let payload: String = rng
+                .sample_iter(&Alphanumeric)
+                .take(PUT_PAYLOAD_LEN)
+                .map(char::from)
+                .collect();
+            client.put(base_url).body(payload).send().await
+        }
+        Method::Delete => client.delete(base_url).send().await,
+    };
+    let response = response.map_err(|e| TestflowError::RpcError(e.to_string()))?;
+    response
+        .error_for_status()
+        .map_err(|e| TestflowError::RpcError(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(pairs: &[(Method, u32)]) -> Vec<config::MethodWeight> {
+        pairs
+            .iter()
+            .map(|(method, weight)| config::MethodWeight {
+                method: *method,
+                weight: *weight,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sample_ratio_roughly_matches_configured_weights() {
+        let distribution = WeightedMethods::new(&weights(&[
+            (Method::Get, 70),
+            (Method::Put, 20),
+            (Method::Delete, 10),
+        ]))
+        .unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+        let samples = 10_000;
+        let mut counts: HashMap<Method, u32> = HashMap::new();
+        for _ in 0..samples {
+            *counts.entry(distribution.sample(&mut rng)).or_default() += 1;
+        }
+
+        let get_ratio = *counts.get(&Method::Get).unwrap_or(&0) as f64 / samples as f64;
+        let put_ratio = *counts.get(&Method::Put).unwrap_or(&0) as f64 / samples as f64;
+        let delete_ratio = *counts.get(&Method::Delete).unwrap_or(&0) as f64 / samples as f64;
+
+        assert!((get_ratio - 0.7).abs() < 0.03, "GET ratio {}", get_ratio);
+        assert!((put_ratio - 0.2).abs() < 0.03, "PUT ratio {}", put_ratio);
+        assert!((delete_ratio - 0.1).abs() < 0.03, "DELETE ratio {}", delete_ratio);
+    }
+
+    #[test]
+    fn single_nonzero_weight_always_wins_over_zero_weight_entries() {
+        let distribution = WeightedMethods::new(&weights(&[
+            (Method::Get, 0),
+            (Method::Put, 5),
+            (Method::Delete, 0),
+        ]))
+        .unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        for _ in 0..100 {
+            assert_eq!(distribution.sample(&mut rng), Method::Put);
+        }
+    }
+
+    #[test]
+    fn new_rejects_empty_weights() {
+        assert!(WeightedMethods::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod common;
+pub mod config;
+pub mod ctx;
+pub mod hotshot;
+pub mod http;
+pub mod metrics;
+pub mod runner;
+pub mod signal;
+pub mod solana;
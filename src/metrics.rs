@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+/// Lower bound of the histogram range: latencies below 1µs are folded into
+/// the first bucket.
+const MIN_NANOS: f64 = 1_000.0;
+/// Upper bound of the histogram range: latencies above 60s are folded into
+/// the last bucket.
+const MAX_NANOS: f64 = 60_000_000_000.0;
+/// Sub-buckets per power-of-two doubling; higher gives finer percentiles at
+/// the cost of more buckets.
+const SUB_BUCKETS_PER_DOUBLING: f64 = 16.0;
+
+/// A fixed-size, logarithmically-bucketed latency histogram. Recording and
+/// merging are both O(1)/O(bucket count) with no allocation, so it is cheap
+/// enough to keep one per round and fold them all into a run-wide summary.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; Self::num_buckets()],
+            count: 0,
+        }
+    }
+
+    fn num_buckets() -> usize {
+        Self::bucket_index(Duration::from_nanos(MAX_NANOS as u64)) + 1
+    }
+
+    fn bucket_index(latency: Duration) -> usize {
+        let nanos = (latency.as_nanos() as f64).clamp(MIN_NANOS, MAX_NANOS);
+        ((nanos / MIN_NANOS).log2() * SUB_BUCKETS_PER_DOUBLING) as usize
+    }
+
+    fn bucket_upper_bound(index: usize) -> Duration {
+        let nanos = MIN_NANOS * 2f64.powf((index + 1) as f64 / SUB_BUCKETS_PER_DOUBLING);
+        Duration::from_nanos(nanos.clamp(MIN_NANOS, MAX_NANOS) as u64)
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let index = Self::bucket_index(latency);
+        self.buckets[index] += 1;
+        self.count += 1;
+    }
+
+    /// Fold `other`'s bucket counts into `self`, e.g. to roll per-round
+    /// histograms up into a run-wide one.
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (a, b) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *a += b;
+        }
+        self.count += other.count;
+    }
+
+    /// Walk cumulative bucket counts until `p` of the total has been
+    /// crossed, returning that bucket's upper bound as the estimate.
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let target = ((self.count as f64) * p).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (index, &bucket_count) in self.buckets.iter().enumerate() {
+            cumulative += bucket_count;
+            if cumulative >= target {
+                return Self::bucket_upper_bound(index);
+            }
+        }
+        Self::bucket_upper_bound(self.buckets.len() - 1)
+    }
+
+    pub fn max(&self) -> Duration {
+        match self.buckets.iter().rposition(|&c| c > 0) {
+            Some(index) => Self::bucket_upper_bound(index),
+            None => Duration::ZERO,
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Summarize this histogram's percentiles and throughput over `elapsed`.
+    pub fn summary(&self, elapsed: Duration) -> LatencySummary {
+        LatencySummary {
+            count: self.count,
+            p50: self.percentile(0.50),
+            p90: self.percentile(0.90),
+            p99: self.percentile(0.99),
+            max: self.max(),
+            throughput: if elapsed.is_zero() {
+                0.0
+            } else {
+                self.count as f64 / elapsed.as_secs_f64()
+            },
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Percentile and throughput summary of a [`LatencyHistogram`], ready to
+/// print or export.
+#[derive(Debug, Clone, Default)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub throughput: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_100_matches_max_and_bounds_the_largest_sample() {
+        let mut hist = LatencyHistogram::new();
+        let samples = [
+            Duration::from_micros(50),
+            Duration::from_millis(1),
+            Duration::from_millis(10),
+            Duration::from_millis(250),
+        ];
+        for s in samples {
+            hist.record(s);
+        }
+        let p100 = hist.percentile(1.0);
+        assert_eq!(p100, hist.max());
+        assert!(p100 >= samples.into_iter().max().unwrap());
+    }
+
+    #[test]
+    fn empty_histogram_reports_zero() {
+        let hist = LatencyHistogram::new();
+        assert_eq!(hist.percentile(0.5), Duration::ZERO);
+        assert_eq!(hist.max(), Duration::ZERO);
+        assert_eq!(hist.count(), 0);
+    }
+
+    #[test]
+    fn merging_two_histograms_matches_recording_every_sample_into_one() {
+        let samples = [
+            Duration::from_micros(10),
+            Duration::from_micros(500),
+            Duration::from_millis(2),
+            Duration::from_millis(20),
+            Duration::from_secs(1),
+        ];
+
+        let mut combined = LatencyHistogram::new();
+        for s in samples {
+            combined.record(s);
+        }
+
+        let mut a = LatencyHistogram::new();
+        let mut b = LatencyHistogram::new();
+        for (i, s) in samples.into_iter().enumerate() {
+            if i % 2 == 0 {
+                a.record(s);
+            } else {
+                b.record(s);
+            }
+        }
+        a.merge(&b);
+
+        assert_eq!(a.count(), combined.count());
+        assert_eq!(a.percentile(0.5), combined.percentile(0.5));
+        assert_eq!(a.percentile(0.99), combined.percentile(0.99));
+        assert_eq!(a.max(), combined.max());
+    }
+
+    #[test]
+    fn bucket_upper_bound_spans_the_full_configured_range() {
+        assert!(LatencyHistogram::bucket_upper_bound(0) >= Duration::from_nanos(MIN_NANOS as u64));
+        let last = LatencyHistogram::num_buckets() - 1;
+        assert_eq!(
+            LatencyHistogram::bucket_upper_bound(last),
+            Duration::from_nanos(MAX_NANOS as u64)
+        );
+    }
+}
@@ -1,70 +1,160 @@
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use tokio::task;
-use tokio::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::signature::Keypair;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{Duration, MissedTickBehavior};
 
-use crate::common::{RoundResults, TestflowError};
+use crate::common::{RoundResults, RunResults, TestflowError};
 use crate::config::{self, Adapter};
-use crate::{ctx, hotshot};
+use crate::metrics;
+use crate::{ctx, hotshot, http, solana};
+
+/// How long `run` waits for in-flight round tasks to finish after a stop
+/// signal (or the last round) before giving up on the stragglers.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
 
 pub async fn load_endpoints(cfg: config::Config) -> Result<Vec<String>, TestflowError> {
     match cfg.adapter {
         Adapter::Hotshot => hotshot::load_endpoints(cfg.args.clone()).await,
-        _ => Err(TestflowError::UnsupportedAdapter(cfg.adapter.to_string())),
+        Adapter::Solana => solana::load_endpoints(cfg.args.clone()).await,
+        Adapter::Http => http::load_endpoints(cfg.args.clone()).await,
     }
 }
 
 /// Run the test flow with the given configuration.
 /// This function will run the test flow until we reach cfg.iterations or if the context is stopped.
-/// Upon completion, we wait for all the open threads to complete. and the function will return a vector of RoundResults.
+/// `config_rx` is watched on every tick so a SIGHUP-triggered reload (see [`crate::signal`]) can
+/// swap in new rounds/round_templates/adapter/iterations without dropping in-flight rounds or
+/// restarting the process. A reload that also changes `interval` or `max_concurrency` rebuilds the
+/// ticker/semaphore too, so those take effect starting with the next tick rather than being silently
+/// ignored for the rest of the run.
+/// On completion or stop, in-flight round tasks are drained (up to `SHUTDOWN_GRACE`) and the
+/// function returns every round that finished, plus the latency summary merged across the run.
 pub async fn run(
     ctx: Arc<ctx::Context>,
-    cfg: config::Config,
+    mut config_rx: watch::Receiver<config::Config>,
     rpc_urls: Vec<String>,
-) -> Result<Vec<RoundResults>, TestflowError> {
+) -> Result<RunResults, TestflowError> {
+    let mut cfg = config_rx.borrow_and_update().clone();
     let mut i: u32 = 0;
     let mut quit = ctx.recv();
-    let results = Arc::new(RwLock::new(Vec::new()));
-    loop {
+    let started = Instant::now();
+    let mut tasks: JoinSet<Option<RoundResults>> = JoinSet::new();
+    // Shared across rounds (and cheap to clone, since reqwest::Client is
+    // Arc-backed internally) so the http adapter keeps pooled connections
+    // alive between rounds instead of reconnecting every time.
+    let http_client = reqwest::Client::new();
+    // Built once and reused across rounds for the same reason as
+    // `http_client`: constructing an `RpcClient` is cheap but re-reading the
+    // payer keypair off disk every round is not. One client per configured
+    // endpoint so the solana adapter can round-robin across all of them.
+    let solana_clients = Arc::new(
+        rpc_urls
+            .iter()
+            .map(|url| Arc::new(RpcClient::new(url.clone())))
+            .collect::<Vec<_>>(),
+    );
+    let mut solana_payers = Arc::new(solana::load_payers(&cfg.round_templates)?);
+    let mut semaphore = Arc::new(Semaphore::new(cfg.max_concurrency.max(1)));
+    let mut ticker = tokio::time::interval(Duration::from_secs(cfg.interval));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    'outer: loop {
+        // Wait for the tick boundary, then release every round due this
+        // tick together instead of trickling them out one sleep at a time.
+        tokio::select! {
+            _ = quit.recv() => {
+                tracing::debug!("ctx stopped while waiting for next tick");
+                break 'outer;
+            }
+            _ = ticker.tick() => {}
+        }
+        if config_rx.has_changed().unwrap_or(false) {
+            let new_cfg = config_rx.borrow_and_update().clone();
+            if new_cfg.max_concurrency != cfg.max_concurrency {
+                tracing::debug!(
+                    "reload changed max_concurrency {} -> {}; rebuilding semaphore",
+                    cfg.max_concurrency,
+                    new_cfg.max_concurrency
+                );
+                semaphore = Arc::new(Semaphore::new(new_cfg.max_concurrency.max(1)));
+            }
+            if new_cfg.interval != cfg.interval {
+                tracing::debug!(
+                    "reload changed interval {}s -> {}s; rebuilding ticker",
+                    cfg.interval,
+                    new_cfg.interval
+                );
+                // `tokio::time::interval` fires its first tick immediately; using it
+                // here would release a second round-batch right on top of the one
+                // that just triggered this reload. Defer the rebuilt ticker's first
+                // tick a full interval out instead.
+                let next_tick = tokio::time::Instant::now() + Duration::from_secs(new_cfg.interval);
+                ticker = tokio::time::interval_at(next_tick, Duration::from_secs(new_cfg.interval));
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            }
+            match solana::load_payers(&new_cfg.round_templates) {
+                Ok(payers) => solana_payers = Arc::new(payers),
+                Err(e) => tracing::warn!(
+                    "reload: failed to reload solana payer keypairs, keeping previous ones: {}",
+                    e
+                ),
+            }
+            cfg = new_cfg;
+            tracing::debug!("applied reloaded config for the next rounds");
+        }
         let rounds = cfg.rounds.clone();
         let mut r: usize = 0;
         for round in rounds {
             let round_templates = cfg.round_templates.clone();
             let rpc_urls = rpc_urls.clone();
-            let results = Arc::clone(&results);
+            let semaphore = Arc::clone(&semaphore);
+            let http_client = http_client.clone();
+            let solana_clients = Arc::clone(&solana_clients);
+            let solana_payers = Arc::clone(&solana_payers);
             i += 1;
             r += 1;
             let iteration = i;
             let round_num = r;
             let adapter = cfg.adapter.clone();
-            tokio::select! {
-                _ = task::spawn(async move {
-                    match process_round(adapter, round, iteration, rpc_urls, round_templates).await {
-                        Ok(result) => {
-                            tracing::debug!("Iteration {} round {} completed", iteration, round_num);
-                            let mut results = results.write().unwrap();
-                            results.push(result);
-                        }
-                        Err(e) => {
-                            tracing::warn!("Iteration {} round {} failed: {}", iteration, round_num, e);
-                        }
-                    }
-                }) => {}
+            // Apply backpressure: don't spawn the next round until a
+            // concurrency permit frees up, capping in-flight round tasks.
+            let permit = tokio::select! {
+                permit = semaphore.clone().acquire_owned() => permit.expect("semaphore closed"),
                 _ = quit.recv() => {
-                    tracing::debug!("Iteration {} round {} timed out as ctx was stopped", iteration, round_num);
-                    break;
+                    tracing::debug!("ctx stopped waiting for a free slot at iteration {} round {}", iteration, round_num);
+                    break 'outer;
                 }
-            }
-            tokio::select! {
-                _ = quit.recv() => {
-                    tracing::debug!("ctx stopped during iteration {} round {}", iteration, round_num);
-                    break;
+            };
+            tasks.spawn(async move {
+                let _permit = permit;
+                match process_round(
+                    adapter,
+                    round,
+                    iteration,
+                    rpc_urls,
+                    round_templates,
+                    http_client,
+                    solana_clients,
+                    solana_payers,
+                )
+                .await
+                {
+                    Ok(result) => {
+                        tracing::debug!("Iteration {} round {} completed", iteration, round_num);
+                        Some(result)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Iteration {} round {} failed: {}", iteration, round_num, e);
+                        None
+                    }
                 }
-                _ = tokio::time::sleep(Duration::from_secs(cfg.interval)) => {}
-            }
+            });
             if let Some(iterations) = cfg.iterations {
                 if i >= iterations as u32 {
-                    break;
+                    break 'outer;
                 }
             }
         }
@@ -74,8 +164,36 @@ pub async fn run(
             }
         }
     }
-    let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
-    Ok(results)
+    // No more rounds will be spawned; drain whatever is still in flight,
+    // bounded so a stuck endpoint can't hang shutdown forever.
+    let mut results = Vec::new();
+    let deadline = tokio::time::Instant::now() + SHUTDOWN_GRACE;
+    loop {
+        tokio::select! {
+            joined = tasks.join_next() => {
+                match joined {
+                    Some(Ok(Some(result))) => results.push(result),
+                    Some(Ok(None)) => {}
+                    Some(Err(e)) => tracing::warn!("round task panicked: {}", e),
+                    None => break,
+                }
+            }
+            _ = tokio::time::sleep_until(deadline) => {
+                tracing::warn!(
+                    "shutdown grace period elapsed with {} round task(s) still in flight; abandoning them",
+                    tasks.len()
+                );
+                tasks.shutdown().await;
+                break;
+            }
+        }
+    }
+    let mut merged = metrics::LatencyHistogram::new();
+    for result in &results {
+        merged.merge(&result.latency);
+    }
+    let latency = merged.summary(started.elapsed());
+    Ok(RunResults { rounds: results, latency })
 }
 
 /// Process a single round, sending transactions to the RPC servers concurrently
@@ -85,11 +203,87 @@ async fn process_round(
     iteration: u32,
     rpc_urls: Vec<String>,
     round_templates: HashMap<String, config::RoundTemplate>,
+    http_client: reqwest::Client,
+    solana_clients: Arc<Vec<Arc<RpcClient>>>,
+    solana_payers: Arc<HashMap<String, Arc<Keypair>>>,
 ) -> Result<RoundResults, TestflowError> {
     match adapter {
         Adapter::Hotshot => {
             hotshot::process_round(round, iteration, rpc_urls, round_templates).await
         }
-        _ => Err(TestflowError::UnsupportedAdapter(adapter.to_string())),
+        Adapter::Solana => {
+            solana::process_round(round, iteration, round_templates, solana_payers, solana_clients)
+                .await
+        }
+        Adapter::Http => {
+            http::process_round(round, iteration, rpc_urls, round_templates, http_client).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Round, RoundTemplate};
+
+    // The hotshot adapter is a stub that does no real I/O, so it drives
+    // `run`'s concurrency/ticker/shutdown machinery without needing a live
+    // endpoint.
+    fn hotshot_config(iterations: usize, max_concurrency: usize, interval: u64) -> Config {
+        let mut round_templates = HashMap::new();
+        round_templates.insert("default".to_string(), RoundTemplate::default());
+        Config {
+            adapter: Adapter::Hotshot,
+            args: HashMap::new(),
+            rounds: vec![Round {
+                template: "default".to_string(),
+            }],
+            round_templates,
+            interval,
+            iterations: Some(iterations),
+            max_concurrency,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_completes_all_iterations_and_returns_every_round_result() {
+        let cfg = hotshot_config(5, 2, 1);
+        let (_config_tx, config_rx) = watch::channel(cfg);
+        let ctx = Arc::new(ctx::Context::new());
+
+        let results = run(ctx, config_rx, vec!["http://localhost:0".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(results.rounds.len(), 5);
+        assert_eq!(results.latency.count, 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn run_drains_in_flight_rounds_and_returns_promptly_once_stopped() {
+        // `interval`'s first tick always fires immediately, so exactly one
+        // round is spawned before the huge interval would next come due;
+        // the stop signal should then short-circuit the wait for the next
+        // tick and `run` should return with that one completed round
+        // instead of hanging until the grace period or the next tick.
+        let cfg = hotshot_config(1_000, 1, 3600);
+        let (_config_tx, config_rx) = watch::channel(cfg);
+        let ctx = Arc::new(ctx::Context::new());
+        let ctx_for_stop = Arc::clone(&ctx);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            ctx_for_stop.stop();
+        });
+
+        let results = tokio::time::timeout(
+            Duration::from_secs(5),
+            run(ctx, config_rx, vec!["http://localhost:0".to_string()]),
+        )
+        .await
+        .expect("run should return promptly after the context is stopped")
+        .unwrap();
+
+        assert_eq!(results.rounds.len(), 1);
     }
 }
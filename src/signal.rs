@@ -1,27 +1,187 @@
-use tokio::{select, signal};
-use tracing::debug;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tracing::{debug, warn};
 
 use crate::common::TestrpcError;
+use crate::config;
+use crate::ctx;
 
-pub async fn wait_exit_signals() -> Result<(), TestrpcError> {
-    let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())
+/// Action requested by an external signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalAction {
+    /// Stop the run.
+    Exit,
+    /// Re-read and apply the config in place.
+    Reload,
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> Result<SignalAction, TestrpcError> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())
+        .map_err(|e| TestrpcError::TerminationError(e.to_string()))?;
+    let mut interrupt = signal(SignalKind::interrupt())
         .map_err(|e| TestrpcError::TerminationError(e.to_string()))?;
-    let mut interrupt = signal::unix::signal(signal::unix::SignalKind::interrupt())
+    let mut quit = signal(SignalKind::quit())
         .map_err(|e| TestrpcError::TerminationError(e.to_string()))?;
-    let mut quit = signal::unix::signal(signal::unix::SignalKind::quit())
+    let mut hangup = signal(SignalKind::hangup())
         .map_err(|e| TestrpcError::TerminationError(e.to_string()))?;
 
-    select! {
+    tokio::select! {
         _ = terminate.recv() => {
             debug!("Received terminate signal");
+            Ok(SignalAction::Exit)
         }
         _ = interrupt.recv() => {
             debug!("Received interrupt signal");
+            Ok(SignalAction::Exit)
         }
         _ = quit.recv() => {
             debug!("Received quit signal");
+            Ok(SignalAction::Exit)
+        }
+        _ = hangup.recv() => {
+            debug!("Received hangup signal, reload requested");
+            Ok(SignalAction::Reload)
         }
     }
+}
+
+#[cfg(windows)]
+async fn wait_for_signal() -> Result<SignalAction, TestrpcError> {
+    tokio::signal::ctrl_c()
+        .await
+        .map_err(|e| TestrpcError::TerminationError(e.to_string()))?;
+    debug!("Received ctrl-c");
+    Ok(SignalAction::Exit)
+}
 
-    Ok(())
+/// Wait for a single exit signal, ignoring reload requests. Kept for callers
+/// that only care about shutdown.
+pub async fn wait_exit_signals() -> Result<(), TestrpcError> {
+    loop {
+        if wait_for_signal().await? == SignalAction::Exit {
+            return Ok(());
+        }
+    }
+}
+
+/// Drive the signal subsystem for the lifetime of a run: on an exit signal,
+/// stop `ctx`; on a reload request (SIGHUP on Unix; unsupported on Windows),
+/// re-read `config_path`, validate it, and publish it on `config_tx` so the
+/// running `runner::run` loop can pick up the new config on its next tick.
+pub async fn watch_signals(
+    ctx: Arc<ctx::Context>,
+    config_path: PathBuf,
+    config_tx: watch::Sender<config::Config>,
+) -> Result<(), TestrpcError> {
+    loop {
+        let action = wait_for_signal().await?;
+        if handle_signal(action, &ctx, &config_path, &config_tx).await.is_break() {
+            return Ok(());
+        }
+    }
+}
+
+/// Apply one signal action: stop `ctx` on `Exit`, or reload+validate+publish
+/// the config on `Reload`. Split out from `watch_signals` so the reload/exit
+/// behavior can be exercised directly in tests without dispatching real OS
+/// signals. Returns `ControlFlow::Break` when the caller should stop.
+async fn handle_signal(
+    action: SignalAction,
+    ctx: &ctx::Context,
+    config_path: &Path,
+    config_tx: &watch::Sender<config::Config>,
+) -> ControlFlow<()> {
+    match action {
+        SignalAction::Exit => {
+            ctx.stop();
+            ControlFlow::Break(())
+        }
+        SignalAction::Reload => {
+            match config::Config::load(config_path) {
+                Ok(new_cfg) => {
+                    debug!("reloaded config from {}", config_path.display());
+                    let _ = config_tx.send(new_cfg);
+                }
+                Err(e) => {
+                    warn!("failed to reload config from {}: {}", config_path.display(), e);
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const VALID_CONFIG: &str = "\
+adapter = \"hotshot\"
+rounds = []
+interval = 1
+max_concurrency = 1
+
+[round_templates]
+";
+
+    fn write_temp_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "testrpc_signal_test_{}_{name}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, contents).expect("failed to write temp config");
+        path
+    }
+
+    #[tokio::test]
+    async fn exit_stops_the_context() {
+        let path = write_temp_config("exit", VALID_CONFIG);
+        let cfg = config::Config::load(&path).unwrap();
+        let ctx = ctx::Context::new();
+        let mut stopped = ctx.recv();
+        let (config_tx, _config_rx) = watch::channel(cfg);
+
+        let flow = handle_signal(SignalAction::Exit, &ctx, Path::new("/unused"), &config_tx).await;
+
+        assert!(flow.is_break());
+        assert!(stopped.try_recv().is_ok());
+        fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn reload_with_a_valid_config_publishes_it() {
+        let path = write_temp_config("reload_ok", VALID_CONFIG);
+        let cfg = config::Config::load(&path).unwrap();
+        let ctx = ctx::Context::new();
+        let (config_tx, mut config_rx) = watch::channel(cfg);
+
+        let flow = handle_signal(SignalAction::Reload, &ctx, &path, &config_tx).await;
+
+        assert!(flow.is_continue());
+        assert!(config_rx.has_changed().unwrap());
+        fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn reload_with_an_invalid_config_keeps_the_previous_one() {
+        let good_path = write_temp_config("reload_bad_initial", VALID_CONFIG);
+        let bad_path = write_temp_config("reload_bad", "not valid toml {{{");
+        let cfg = config::Config::load(&good_path).unwrap();
+        let ctx = ctx::Context::new();
+        let (config_tx, mut config_rx) = watch::channel(cfg);
+
+        let flow = handle_signal(SignalAction::Reload, &ctx, &bad_path, &config_tx).await;
+
+        assert!(flow.is_continue());
+        assert!(!config_rx.has_changed().unwrap_or(false));
+        fs::remove_file(&good_path).ok();
+        fs::remove_file(&bad_path).ok();
+    }
 }
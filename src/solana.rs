@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+use crate::common::{RoundResults, TestflowError};
+use crate::config::{self, SolanaOperation};
+use crate::metrics;
+
+const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+const MEMO_PAYLOAD_LEN: usize = 32;
+
+pub async fn load_endpoints(args: HashMap<String, String>) -> Result<Vec<String>, TestflowError> {
+    let endpoints = args
+        .get("endpoints")
+        .ok_or_else(|| TestflowError::InvalidConfig("missing `endpoints` arg".into()))?;
+    Ok(endpoints.split(',').map(|s| s.trim().to_string()).collect())
+}
+
+/// Read every round template's payer keypair from disk once, keyed by its
+/// configured path (so templates sharing a payer only read it once), instead
+/// of re-reading it from disk on every round. Called once by `runner::run`
+/// at startup and again whenever a reloaded config is picked up.
+pub fn load_payers(
+    round_templates: &HashMap<String, config::RoundTemplate>,
+) -> Result<HashMap<String, Arc<Keypair>>, TestflowError> {
+    let mut payers = HashMap::new();
+    for template in round_templates.values() {
+        let Some(path) = template.payer_keypair_path.as_ref() else {
+            continue;
+        };
+        if payers.contains_key(path) {
+            continue;
+        }
+        let keypair = read_keypair_file(path).map_err(|e| {
+            TestflowError::InvalidConfig(format!("failed to read payer keypair {path}: {e}"))
+        })?;
+        payers.insert(path.clone(), Arc::new(keypair));
+    }
+    Ok(payers)
+}
+
+/// Generate and submit one synthetic transaction for a round, deterministically
+/// derived from `iteration` so runs are reproducible. `payers` and `clients`
+/// are built once by the caller and reused across rounds rather than reading
+/// the keypair file or dialing an RPC endpoint on every round; `clients` holds
+/// one `RpcClient` per configured endpoint and this round round-robins across
+/// them by `iteration` so a multi-endpoint config spreads load instead of
+/// always hitting the first endpoint.
+pub async fn process_round(
+    round: config::Round,
+    iteration: u32,
+    round_templates: HashMap<String, config::RoundTemplate>,
+    payers: Arc<HashMap<String, Arc<Keypair>>>,
+    clients: Arc<Vec<Arc<RpcClient>>>,
+) -> Result<RoundResults, TestflowError> {
+    let template = round_templates.get(&round.template).ok_or_else(|| {
+        TestflowError::InvalidConfig(format!("unknown round template `{}`", round.template))
+    })?;
+    let client = clients
+        .get(iteration as usize % clients.len().max(1))
+        .ok_or_else(|| TestflowError::InvalidConfig("no rpc endpoints configured".into()))?;
+
+    let payer_path = template.payer_keypair_path.as_ref().ok_or_else(|| {
+        TestflowError::InvalidConfig("solana round template missing `payer_keypair_path`".into())
+    })?;
+    let payer = payers.get(payer_path).ok_or_else(|| {
+        TestflowError::InvalidConfig(format!("payer keypair `{payer_path}` was not preloaded"))
+    })?;
+
+    let round_started = Instant::now();
+    let mut rng = ChaCha8Rng::seed_from_u64(iteration as u64);
+    let blockhash = client
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| TestflowError::RpcError(e.to_string()))?;
+
+    let instruction = build_instruction(template, &payer.pubkey(), &mut rng)?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer.as_ref()],
+        blockhash,
+    );
+
+    let mut result = RoundResults {
+        iteration,
+        ..Default::default()
+    };
+    let started = Instant::now();
+    let outcome = client.send_and_confirm_transaction(&tx).await;
+    result.latency.record(started.elapsed());
+    match outcome {
+        Ok(signature) => {
+            tracing::debug!("iteration {} signature {} confirmed", iteration, signature);
+            result.signature = Some(signature.to_string());
+            result.successes = 1;
+        }
+        Err(e) => {
+            tracing::warn!("iteration {} transaction failed: {}", iteration, e);
+            result.failures = 1;
+        }
+    }
+    result.summary = result.latency.summary(round_started.elapsed());
+    Ok(result)
+}
+
+/// Build the instruction for one round's transaction, deterministically from
+/// `rng`. Split out from `process_round` so this selection/payload logic can
+/// be tested without a live RPC endpoint.
+fn build_instruction(
+    template: &config::RoundTemplate,
+    payer: &Pubkey,
+    rng: &mut ChaCha8Rng,
+) -> Result<Instruction, TestflowError> {
+    let operation = template
+        .solana_operation
+        .clone()
+        .unwrap_or(SolanaOperation::Memo);
+    match operation {
+        SolanaOperation::Memo => {
+            let payload: String = rng
+                .sample_iter(&Alphanumeric)
+                .take(MEMO_PAYLOAD_LEN)
+                .map(char::from)
+                .collect();
+            let memo_program = Pubkey::from_str(MEMO_PROGRAM_ID)
+                .map_err(|e| TestflowError::InvalidConfig(e.to_string()))?;
+            Ok(Instruction::new_with_bytes(
+                memo_program,
+                payload.as_bytes(),
+                vec![AccountMeta::new_readonly(*payer, true)],
+            ))
+        }
+        SolanaOperation::Transfer => {
+            let recipient = template.recipient.as_ref().ok_or_else(|| {
+                TestflowError::InvalidConfig("transfer round template missing `recipient`".into())
+            })?;
+            let recipient = Pubkey::from_str(recipient)
+                .map_err(|e| TestflowError::InvalidConfig(e.to_string()))?;
+            let lamports = template.transfer_lamports.unwrap_or(1);
+            Ok(system_instruction::transfer(payer, &recipient, lamports))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(
+        operation: Option<SolanaOperation>,
+        recipient: Option<&str>,
+        transfer_lamports: Option<u64>,
+    ) -> config::RoundTemplate {
+        config::RoundTemplate {
+            solana_operation: operation,
+            payer_keypair_path: None,
+            recipient: recipient.map(String::from),
+            transfer_lamports,
+            method_weights: Vec::new(),
+            requests_per_round: None,
+        }
+    }
+
+    #[test]
+    fn memo_instruction_targets_the_memo_program_and_is_signed_by_the_payer() {
+        let payer = Keypair::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let instruction =
+            build_instruction(&template(Some(SolanaOperation::Memo), None, None), &payer.pubkey(), &mut rng)
+                .unwrap();
+
+        assert_eq!(
+            instruction.program_id,
+            Pubkey::from_str(MEMO_PROGRAM_ID).unwrap()
+        );
+        assert_eq!(instruction.accounts.len(), 1);
+        assert_eq!(instruction.accounts[0].pubkey, payer.pubkey());
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.data.len(), MEMO_PAYLOAD_LEN);
+    }
+
+    #[test]
+    fn memo_payload_is_deterministic_for_a_given_seed() {
+        let payer = Keypair::new();
+        let template = template(Some(SolanaOperation::Memo), None, None);
+
+        let mut rng_a = ChaCha8Rng::seed_from_u64(42);
+        let a = build_instruction(&template, &payer.pubkey(), &mut rng_a).unwrap();
+        let mut rng_b = ChaCha8Rng::seed_from_u64(42);
+        let b = build_instruction(&template, &payer.pubkey(), &mut rng_b).unwrap();
+
+        assert_eq!(a.data, b.data);
+    }
+
+    #[test]
+    fn transfer_instruction_moves_lamports_to_the_configured_recipient() {
+        let payer = Keypair::new();
+        let recipient = Keypair::new().pubkey();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let instruction = build_instruction(
+            &template(Some(SolanaOperation::Transfer), Some(&recipient.to_string()), Some(500)),
+            &payer.pubkey(),
+            &mut rng,
+        )
+        .unwrap();
+
+        assert!(instruction
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == recipient));
+        assert_eq!(
+            instruction,
+            system_instruction::transfer(&payer.pubkey(), &recipient, 500)
+        );
+    }
+
+    #[test]
+    fn transfer_without_recipient_is_an_error() {
+        let payer = Keypair::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let result = build_instruction(
+            &template(Some(SolanaOperation::Transfer), None, None),
+            &payer.pubkey(),
+            &mut rng,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn defaults_to_memo_when_operation_is_unset() {
+        let payer = Keypair::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let instruction = build_instruction(&template(None, None, None), &payer.pubkey(), &mut rng).unwrap();
+        assert_eq!(
+            instruction.program_id,
+            Pubkey::from_str(MEMO_PROGRAM_ID).unwrap()
+        );
+    }
+}